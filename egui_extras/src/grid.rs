@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+
+use egui::{vec2, Pos2, Rect, Response, Sense, Ui, Vec2};
+
+use crate::{sizing::Sizing, Size};
+
+/// Builder for creating a new [`Grid`]: a 2D layout with shared column widths across
+/// rows, unlike [`crate::StripBuilder`] which is strictly 1D.
+pub struct GridBuilder<'a> {
+    ui: &'a mut Ui,
+    col_sizing: Sizing,
+    row_sizing: Sizing,
+}
+
+impl<'a> GridBuilder<'a> {
+    /// Create new grid builder.
+    ///
+    /// After adding column sizes with [`Self::column`]/[`Self::columns`] (and,
+    /// optionally, row heights with [`Self::new_row`]/[`Self::new_rows`]), build the
+    /// grid with [`Self::show`].
+    pub fn new(ui: &'a mut Ui) -> Self {
+        Self {
+            ui,
+            col_sizing: Sizing::new(),
+            row_sizing: Sizing::new(),
+        }
+    }
+
+    /// Add size hint for a column.
+    pub fn column(mut self, width: Size) -> Self {
+        self.col_sizing.add(width);
+        self
+    }
+
+    /// Add size hint for `count` columns.
+    pub fn columns(mut self, width: Size, count: usize) -> Self {
+        for _ in 0..count {
+            self.col_sizing.add(width);
+        }
+        self
+    }
+
+    /// Add a size hint for the next row. Rows that are never declared this way (because
+    /// [`Grid::cell`] wrapped past the end of the list) fall back to the `ui`'s default
+    /// row height.
+    pub fn new_row(mut self, height: Size) -> Self {
+        self.row_sizing.add(height);
+        self
+    }
+
+    /// Add a size hint for the next `count` rows.
+    pub fn new_rows(mut self, height: Size, count: usize) -> Self {
+        for _ in 0..count {
+            self.row_sizing.add(height);
+        }
+        self
+    }
+
+    /// Build the grid.
+    ///
+    /// Returns a [`egui::Response`] for hover events.
+    pub fn show<F>(self, build: F) -> Response
+    where
+        F: for<'b> FnOnce(Grid<'a, 'b>),
+    {
+        let spacing = self.ui.spacing().item_spacing;
+        let rect = self.ui.available_rect_before_wrap();
+        let default_row_height = self.ui.spacing().interact_size.y;
+
+        let col_widths = self
+            .col_sizing
+            .into_lengths(rect.width() - spacing.x, spacing.x);
+        let row_heights = self
+            .row_sizing
+            .into_lengths(rect.height() - spacing.y, spacing.y);
+
+        let mut layout = GridLayout {
+            ui: self.ui,
+            origin: rect.min,
+            spacing,
+            default_row_height,
+            num_cols: col_widths.len(),
+            col_x: prefix_sums(&col_widths, spacing.x),
+            row_y: prefix_sums(&row_heights, spacing.y),
+            row_heights,
+            occupied: HashSet::new(),
+            cursor: (0, 0),
+            rows_used: 0,
+        };
+
+        build(Grid {
+            layout: &mut layout,
+        });
+
+        layout.finish()
+    }
+}
+
+/// Running total (prefix sum) of `lengths`, with `spacing` between each entry.
+/// `result[i]` is the offset of the *start* of `lengths[i]`, and the final entry is the
+/// offset one `spacing` past the end of the last one.
+fn prefix_sums(lengths: &[f32], spacing: f32) -> Vec<f32> {
+    let mut offsets = Vec::with_capacity(lengths.len() + 1);
+    let mut x = 0.0;
+    for &len in lengths {
+        offsets.push(x);
+        x += len + spacing;
+    }
+    offsets.push(x);
+    offsets
+}
+
+/// Shared, mutable state for a [`Grid`] in progress; lives in [`GridBuilder::show`] so
+/// it survives the whole `build` callback while [`Grid`] itself is just a borrow of it.
+struct GridLayout<'a> {
+    ui: &'a mut Ui,
+    origin: Pos2,
+    spacing: Vec2,
+    default_row_height: f32,
+    num_cols: usize,
+    col_x: Vec<f32>,
+    row_y: Vec<f32>,
+    row_heights: Vec<f32>,
+    /// `(row, col)` pairs already covered by a previous spanning cell.
+    occupied: HashSet<(usize, usize)>,
+    cursor: (usize, usize),
+    rows_used: usize,
+}
+
+impl<'a> GridLayout<'a> {
+    /// Grow `row_y`/`row_heights` with the default row height until `row` is covered.
+    fn ensure_row(&mut self, row: usize) {
+        while self.row_heights.len() <= row {
+            let y = *self.row_y.last().unwrap();
+            self.row_heights.push(self.default_row_height);
+            self.row_y.push(y + self.default_row_height + self.spacing.y);
+        }
+    }
+
+    fn advance_cursor(&mut self, col_span: usize, row_span: usize) {
+        let (row, col) = self.cursor;
+        self.rows_used = self.rows_used.max(row + row_span);
+
+        let mut next_col = col + col_span;
+        let mut next_row = row;
+        if next_col >= self.num_cols {
+            next_col = 0;
+            next_row += 1;
+        }
+        while self.occupied.contains(&(next_row, next_col)) {
+            next_col += 1;
+            if next_col >= self.num_cols {
+                next_col = 0;
+                next_row += 1;
+            }
+        }
+        self.cursor = (next_row, next_col);
+    }
+
+    fn cell_rect(&mut self, col: usize, col_span: usize, row: usize, row_span: usize) -> Rect {
+        self.ensure_row(row + row_span - 1);
+
+        let x0 = self.col_x[col];
+        let x1 = self.col_x[col + col_span] - self.spacing.x;
+        let y0 = self.row_y[row];
+        let y1 = self.row_y[row + row_span] - self.spacing.y;
+        Rect::from_min_max(self.origin + vec2(x0, y0), self.origin + vec2(x1, y1))
+    }
+
+    fn finish(mut self) -> Response {
+        self.ensure_row(self.rows_used.saturating_sub(1));
+        let used_height = (self.row_y.get(self.rows_used).copied().unwrap_or(0.0) - self.spacing.y).max(0.0);
+        let used_width = (*self.col_x.last().unwrap_or(&0.0) - self.spacing.x).max(0.0);
+        let used_rect = Rect::from_min_size(self.origin, vec2(used_width, used_height));
+        self.ui.allocate_rect(used_rect, Sense::hover())
+    }
+}
+
+/// A 2D grid of cells with shared column widths across rows. Cells are added
+/// left-to-right, auto-wrapping to the next row, with [`Self::cell_span`] letting a
+/// cell cover multiple adjacent columns and/or rows.
+pub struct Grid<'a, 'b> {
+    layout: &'b mut GridLayout<'a>,
+}
+
+impl<'a, 'b> Grid<'a, 'b> {
+    /// Skip a single cell without adding any content to it.
+    pub fn empty(&mut self) {
+        self.layout.advance_cursor(1, 1);
+    }
+
+    /// Add a cell occupying a single column and row.
+    pub fn cell(&mut self, add_contents: impl FnOnce(&mut Ui)) {
+        self.cell_span(1, 1, add_contents);
+    }
+
+    /// Add a cell that spans `col_span` columns and `row_span` rows. The cell's width
+    /// is the sum of its covered column widths plus the spacing between them, and
+    /// likewise for its height across the covered rows.
+    pub fn cell_span(
+        &mut self,
+        col_span: usize,
+        row_span: usize,
+        add_contents: impl FnOnce(&mut Ui),
+    ) {
+        assert!(
+            col_span >= 1 && row_span >= 1,
+            "spans must cover at least one cell"
+        );
+        let (row, col) = self.layout.cursor;
+        assert!(
+            col + col_span <= self.layout.num_cols,
+            "cell_span({col_span}, _) at column {col} overflows the grid's {} columns",
+            self.layout.num_cols
+        );
+        for r in row..row + row_span {
+            for c in col..col + col_span {
+                assert!(
+                    !self.layout.occupied.contains(&(r, c)),
+                    "cell_span({col_span}, {row_span}) at ({row}, {col}) overlaps cell ({r}, {c}), \
+                     already reserved by an earlier spanning cell"
+                );
+            }
+        }
+
+        for r in row..row + row_span {
+            for c in col..col + col_span {
+                self.layout.occupied.insert((r, c));
+            }
+        }
+
+        let rect = self.layout.cell_rect(col, col_span, row, row_span);
+        let mut child_ui = self.layout.ui.child_ui(rect, *self.layout.ui.layout());
+        add_contents(&mut child_ui);
+
+        self.layout.advance_cursor(col_span, row_span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GridBuilder, Size};
+
+    #[test]
+    fn cells_wrap_and_spans_reserve_their_footprint() {
+        egui::__run_test_ui(|ui| {
+            let mut order = Vec::new();
+            GridBuilder::new(ui)
+                .columns(Size::Absolute(50.0), 3)
+                .show(|mut grid| {
+                    grid.cell_span(2, 1, |_ui| order.push("wide"));
+                    grid.cell(|_ui| order.push("a"));
+                    grid.cell(|_ui| order.push("b"));
+                    grid.cell(|_ui| order.push("c"));
+                });
+            assert_eq!(order, vec!["wide", "a", "b", "c"]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps cell")]
+    fn row_span_conflicting_with_a_later_column_span_panics() {
+        egui::__run_test_ui(|ui| {
+            GridBuilder::new(ui)
+                .columns(Size::Absolute(50.0), 3)
+                .show(|mut grid| {
+                    grid.cell(|_ui| {});
+                    grid.cell_span(1, 2, |_ui| {}); // reserves (0, 1) and (1, 1).
+                    grid.cell(|_ui| {});
+                    // Now on row 1, a 3-wide span would overlap (1, 1) from above.
+                    grid.cell_span(3, 1, |_ui| {});
+                });
+        });
+    }
+}
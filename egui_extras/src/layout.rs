@@ -0,0 +1,139 @@
+use egui::{vec2, Rect, Response, Sense, Ui};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CellDirection {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CellSize {
+    /// This cell has a fixed size along the [`CellDirection`] the layout grows.
+    Absolute(f32),
+
+    /// This cell fills up all the available space in the layout, in both directions.
+    Remainder,
+}
+
+/// Positions cells of a [`crate::Strip`] one after another, growing in a single
+/// [`CellDirection`].
+pub(crate) struct Layout<'l> {
+    ui: &'l mut Ui,
+    direction: CellDirection,
+    rect: Rect,
+    cursor: f32,
+    max_used: f32,
+}
+
+impl<'l> Layout<'l> {
+    pub(crate) fn new(ui: &'l mut Ui, direction: CellDirection) -> Self {
+        let rect = ui.available_rect_before_wrap();
+        Self {
+            ui,
+            direction,
+            rect,
+            cursor: 0.0,
+            max_used: 0.0,
+        }
+    }
+
+    fn cell_rect(&self, width: &CellSize, height: &CellSize) -> Rect {
+        match self.direction {
+            CellDirection::Horizontal => Rect::from_min_size(
+                self.rect.min + vec2(self.cursor, 0.0),
+                vec2(
+                    match width {
+                        CellSize::Absolute(w) => *w,
+                        CellSize::Remainder => self.rect.width() - self.cursor,
+                    },
+                    match height {
+                        CellSize::Absolute(h) => *h,
+                        CellSize::Remainder => self.rect.height(),
+                    },
+                ),
+            ),
+            CellDirection::Vertical => Rect::from_min_size(
+                self.rect.min + vec2(0.0, self.cursor),
+                vec2(
+                    match width {
+                        CellSize::Absolute(w) => *w,
+                        CellSize::Remainder => self.rect.width(),
+                    },
+                    match height {
+                        CellSize::Absolute(h) => *h,
+                        CellSize::Remainder => self.rect.height() - self.cursor,
+                    },
+                ),
+            ),
+        }
+    }
+
+    fn advance_cursor(&mut self, width: &CellSize, height: &CellSize) {
+        let item_spacing = self.ui.spacing().item_spacing;
+        match self.direction {
+            CellDirection::Horizontal => {
+                if let CellSize::Absolute(w) = width {
+                    self.cursor += w + item_spacing.x;
+                }
+            }
+            CellDirection::Vertical => {
+                if let CellSize::Absolute(h) = height {
+                    self.cursor += h + item_spacing.y;
+                }
+            }
+        }
+        self.max_used = self.max_used.max(self.cursor);
+    }
+
+    /// Skip a cell without adding any content to it.
+    pub(crate) fn empty(&mut self, width: CellSize, height: CellSize) {
+        self.advance_cursor(&width, &height);
+    }
+
+    /// Add a cell with content. Returns the rect the cell was given, so callers can
+    /// e.g. paint a border around it.
+    pub(crate) fn add(
+        &mut self,
+        width: CellSize,
+        height: CellSize,
+        clip: bool,
+        add_contents: impl FnOnce(&mut Ui),
+    ) -> Rect {
+        let child_rect = self.cell_rect(&width, &height);
+
+        let mut child_ui = self.ui.child_ui(child_rect, *self.ui.layout());
+        if clip {
+            let mut clip_rect = child_ui.clip_rect();
+            clip_rect = clip_rect.intersect(child_rect);
+            child_ui.set_clip_rect(clip_rect);
+        }
+        add_contents(&mut child_ui);
+
+        self.advance_cursor(&width, &height);
+        child_rect
+    }
+
+    /// The [`Ui`] the strip is being laid out in, for painting over the whole strip
+    /// (e.g. separators) after all cells have been added.
+    pub(crate) fn ui(&mut self) -> &mut Ui {
+        self.ui
+    }
+
+    /// The full rect available to the strip, for painting separators that span it.
+    pub(crate) fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Allocate the space used by the whole strip in the parent [`Ui`].
+    pub(crate) fn set_rect(self) -> Response {
+        let used_rect = match self.direction {
+            CellDirection::Horizontal => {
+                Rect::from_min_size(self.rect.min, vec2(self.max_used, self.rect.height()))
+            }
+            CellDirection::Vertical => {
+                Rect::from_min_size(self.rect.min, vec2(self.rect.width(), self.max_used))
+            }
+        };
+        self.ui.allocate_rect(used_rect, Sense::hover())
+    }
+}
@@ -0,0 +1,16 @@
+//! This crate adds some extra functionality on top of [`egui`](https://docs.rs/egui).
+//!
+//! This crate is mostly needed for those widgets that are too specialized to
+//! belong in the main `egui` crate.
+
+#![allow(clippy::float_cmp)]
+
+mod grid;
+mod layout;
+mod size;
+mod sizing;
+mod strip;
+
+pub use grid::{Grid, GridBuilder};
+pub use size::Size;
+pub use strip::{Strip, StripBuilder};
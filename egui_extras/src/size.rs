@@ -0,0 +1,58 @@
+/// Size hint for column/row in the [`crate::StripBuilder`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Size {
+    /// Absolute size in points, independent of the available space.
+    Absolute(f32),
+
+    /// Take all the remaining space.
+    Remainder,
+
+    /// Take all the remaining space, but at least this much.
+    RemainderMinimum(f32),
+
+    /// A fraction (`0.0..=1.0`) of the total available length.
+    Percentage(f32),
+
+    /// A fraction of the total available length, expressed as `numerator / denominator`.
+    Ratio(u32, u32),
+
+    /// Take a share of the leftover space, but never less than this.
+    Min(f32),
+
+    /// Take a share of the leftover space, but never more than this.
+    Max(f32),
+}
+
+impl Size {
+    /// The fraction of the total available length this size resolves to, if fixed.
+    pub(crate) fn fraction(&self) -> Option<f32> {
+        match *self {
+            Self::Percentage(fraction) => Some(fraction),
+            Self::Ratio(numerator, denominator) if denominator > 0 => {
+                Some(numerator as f32 / denominator as f32)
+            }
+            Self::Ratio(_, _) => Some(0.0),
+            _ => None,
+        }
+    }
+}
+
+impl std::hash::Hash for Size {
+    /// Manual impl since `f32` isn't [`Hash`] — used to key the sizing cache in
+    /// [`crate::sizing::Sizing::into_lengths_cached`].
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Self::Absolute(value)
+            | Self::RemainderMinimum(value)
+            | Self::Percentage(value)
+            | Self::Min(value)
+            | Self::Max(value) => value.to_bits().hash(state),
+            Self::Ratio(numerator, denominator) => {
+                numerator.hash(state);
+                denominator.hash(state);
+            }
+            Self::Remainder => {}
+        }
+    }
+}
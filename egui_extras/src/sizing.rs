@@ -0,0 +1,274 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use egui::{Id, Ui};
+
+use crate::Size;
+
+/// How many distinct `(sizes, length, spacing)` combinations each call site's
+/// [`LengthCache`] remembers before evicting its least-recently-used entry. Kept small
+/// since a single strip rarely cycles through more than a couple of distinct sizes
+/// (e.g. while the window is being resized).
+const CACHE_CAPACITY_PER_ID: usize = 8;
+
+/// An ordered list of [`Size`] hints, resolved into concrete lengths on demand.
+#[derive(Clone, Default)]
+pub(crate) struct Sizing {
+    pub(crate) sizes: Vec<Size>,
+}
+
+impl Sizing {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add(&mut self, size: Size) {
+        self.sizes.push(size);
+    }
+
+    /// Resolve the size hints into concrete, non-negative lengths that fit within
+    /// `length`, accounting for `spacing` between cells. If every cell's fixed/minimum
+    /// footprint doesn't fit, everything shrinks proportionally instead of going
+    /// negative.
+    pub(crate) fn into_lengths(self, length: f32, spacing: f32) -> Vec<f32> {
+        let n = self.sizes.len();
+        let available = (length - spacing * n.saturating_sub(1) as f32).max(0.0);
+
+        // Every cell's floor: `Absolute`/`Percentage`/`Ratio` are pinned there,
+        // `Min`/`RemainderMinimum` need at least their bound, `Remainder`/`Max` can
+        // start at zero. `flexible` lists the cells still free to grow past their floor.
+        let mut floor = vec![0.0_f32; n];
+        let mut flexible = Vec::new();
+        for (i, &size) in self.sizes.iter().enumerate() {
+            floor[i] = match size {
+                Size::Absolute(width) => width,
+                Size::RemainderMinimum(at_least) | Size::Min(at_least) => {
+                    flexible.push(i);
+                    at_least
+                }
+                Size::Max(_) | Size::Remainder => {
+                    flexible.push(i);
+                    0.0
+                }
+                _ => size.fraction().map_or(0.0, |fraction| available * fraction),
+            };
+        }
+
+        let floor_total: f32 = floor.iter().sum();
+        if floor_total > available {
+            // Over-subscribed: not even the floors fit. Shrink every cell by the same
+            // ratio rather than letting any cell, or the leftover, go negative.
+            let scale = if floor_total > 0.0 {
+                available / floor_total
+            } else {
+                0.0
+            };
+            return floor.into_iter().map(|f| (f * scale).max(0.0)).collect();
+        }
+
+        let mut resolved = floor;
+        let mut leftover = available - floor_total;
+
+        // Water-fill the leftover space evenly across the flexible cells, on top of
+        // their floor. A cell with a `Max` cap is clamped and removed from `flexible`,
+        // and the extra it didn't use is redistributed across whatever cells are still
+        // free. This converges in at most `flexible.len()` passes, since each pass
+        // either converges or clamps at least one more cell.
+        while !flexible.is_empty() && leftover > 0.0 {
+            let share = leftover / flexible.len() as f32;
+            let mut still_flexible = Vec::new();
+            let mut any_clamped = false;
+
+            for &i in &flexible {
+                let extra = if let Size::Max(at_most) = self.sizes[i] {
+                    share.min((at_most - resolved[i]).max(0.0))
+                } else {
+                    share
+                };
+
+                if extra == share {
+                    still_flexible.push(i);
+                } else {
+                    any_clamped = true;
+                    resolved[i] += extra;
+                    leftover -= extra;
+                }
+            }
+
+            if !any_clamped {
+                for &i in &still_flexible {
+                    resolved[i] += share;
+                }
+                break;
+            }
+            flexible = still_flexible;
+            leftover = leftover.max(0.0);
+        }
+
+        resolved
+    }
+
+    /// Like [`Self::into_lengths`], but memoizes the result in `ui`'s memory keyed on
+    /// `ui.id()` plus the size hints and `length`/`spacing`, so a strip with unchanged
+    /// constraints and available space skips the solve on subsequent frames. Each call
+    /// site gets its own small bounded cache, so unrelated strips elsewhere in the UI
+    /// can't evict each other's entries.
+    pub(crate) fn into_lengths_cached(self, ui: &Ui, length: f32, spacing: f32) -> Vec<f32> {
+        let id = ui.id();
+        let key = cache_key(&self.sizes, length, spacing);
+
+        ui.ctx().data_mut(|data| {
+            let caches = data.get_temp_mut_or_insert_with(Id::NULL, SizingCache::default);
+            let cache = caches.per_id.entry(id).or_default();
+
+            if let Some(lengths) = cache.get(key) {
+                return lengths;
+            }
+
+            let lengths = self.into_lengths(length, spacing);
+            cache.insert(key, lengths.clone());
+            lengths
+        })
+    }
+}
+
+/// Hash the size hints together with the quantized `length`/`spacing` they're being
+/// resolved against. Lengths are quantized to a quarter point so that imperceptible
+/// float jitter between frames doesn't miss the cache.
+fn cache_key(sizes: &[Size], length: f32, spacing: f32) -> u64 {
+    fn quantize(value: f32) -> i64 {
+        (value * 4.0).round() as i64
+    }
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    sizes.hash(&mut hasher);
+    quantize(length).hash(&mut hasher);
+    quantize(spacing).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// All of this crate's [`LengthCache`]s, one per distinct `ui.id()` that's called
+/// [`Sizing::into_lengths_cached`], stored once in egui's temporary memory, which
+/// survives across frames but is not persisted.
+#[derive(Clone, Default)]
+struct SizingCache {
+    per_id: HashMap<Id, LengthCache>,
+}
+
+/// A small bounded LRU cache of already-solved [`Sizing::into_lengths`] results for a
+/// single call site.
+#[derive(Clone, Default)]
+struct LengthCache {
+    /// Least-recently-used first.
+    order: VecDeque<u64>,
+    entries: HashMap<u64, Vec<f32>>,
+}
+
+impl LengthCache {
+    fn get(&mut self, key: u64) -> Option<Vec<f32>> {
+        let lengths = self.entries.get(&key)?.clone();
+        self.touch(key);
+        Some(lengths)
+    }
+
+    fn insert(&mut self, key: u64, lengths: Vec<f32>) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= CACHE_CAPACITY_PER_ID {
+            if let Some(lru) = self.order.pop_front() {
+                self.entries.remove(&lru);
+            }
+        }
+        self.entries.insert(key, lengths);
+        self.touch(key);
+    }
+
+    /// Move `key` to the most-recently-used end.
+    fn touch(&mut self, key: u64) {
+        self.order.retain(|&k| k != key);
+        self.order.push_back(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizing(sizes: impl IntoIterator<Item = Size>) -> Sizing {
+        let mut sizing = Sizing::new();
+        for size in sizes {
+            sizing.add(size);
+        }
+        sizing
+    }
+
+    #[test]
+    fn remainder_splits_evenly() {
+        let lengths = sizing([Size::Remainder, Size::Remainder]).into_lengths(100.0, 0.0);
+        assert_eq!(lengths, vec![50.0, 50.0]);
+    }
+
+    #[test]
+    fn over_subscribed_minimums_shrink_instead_of_going_negative() {
+        let lengths = sizing([Size::Min(300.0), Size::Min(300.0), Size::Remainder])
+            .into_lengths(400.0, 0.0);
+        assert!(lengths.iter().all(|&w| w >= 0.0), "{lengths:?}");
+        assert!((lengths.iter().sum::<f32>() - 400.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn over_subscribed_absolutes_shrink_proportionally() {
+        let lengths =
+            sizing([Size::Absolute(300.0), Size::Absolute(300.0)]).into_lengths(400.0, 0.0);
+        assert!(lengths.iter().all(|&w| w >= 0.0), "{lengths:?}");
+        assert_eq!(lengths, vec![200.0, 200.0]);
+    }
+
+    #[test]
+    fn percentage_and_ratio_take_a_fraction_of_the_original_length() {
+        let lengths =
+            sizing([Size::Percentage(0.25), Size::Ratio(1, 4), Size::Remainder])
+                .into_lengths(200.0, 0.0);
+        assert_eq!(lengths, vec![50.0, 50.0, 100.0]);
+    }
+
+    #[test]
+    fn max_caps_a_cell_and_redistributes_the_rest() {
+        let lengths = sizing([Size::Max(20.0), Size::Remainder]).into_lengths(100.0, 0.0);
+        assert_eq!(lengths, vec![20.0, 80.0]);
+    }
+
+    #[test]
+    fn lru_cache_evicts_oldest_entry_past_capacity() {
+        let mut cache = LengthCache::default();
+        for key in 0..CACHE_CAPACITY_PER_ID as u64 {
+            cache.insert(key, vec![key as f32]);
+        }
+        assert!(cache.get(0).is_some());
+
+        cache.insert(CACHE_CAPACITY_PER_ID as u64, vec![0.0]);
+        assert!(
+            cache.get(1).is_none(),
+            "key `1` should have been the least-recently-used entry evicted"
+        );
+        assert!(cache.get(0).is_some(), "key `0` was touched, so it should survive");
+    }
+
+    #[test]
+    fn per_id_caches_dont_evict_each_other() {
+        let mut caches = SizingCache::default();
+        let a = Id::new("strip a");
+        let b = Id::new("strip b");
+
+        // Fill `a`'s cache to capacity and beyond.
+        for key in 0..=CACHE_CAPACITY_PER_ID as u64 {
+            caches.per_id.entry(a).or_default().insert(key, vec![key as f32]);
+        }
+        // `b` gets a single, unrelated entry.
+        caches.per_id.entry(b).or_default().insert(0, vec![100.0]);
+
+        assert_eq!(
+            caches.per_id.get_mut(&b).unwrap().get(0),
+            Some(vec![100.0]),
+            "`a` overflowing its own cache must not touch `b`'s entry"
+        );
+    }
+}
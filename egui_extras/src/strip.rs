@@ -3,12 +3,14 @@ use crate::{
     sizing::Sizing,
     Size,
 };
-use egui::{Response, Ui};
+use egui::{pos2, vec2, Context, Direction, Id, Pos2, Rect, Response, Stroke, Ui};
 
 /// Builder for creating a new [`Strip`].
 pub struct StripBuilder<'a> {
     ui: &'a mut Ui,
     sizing: Sizing,
+    separator: Option<Stroke>,
+    separator_frame: bool,
 }
 
 impl<'a> StripBuilder<'a> {
@@ -45,7 +47,12 @@ impl<'a> StripBuilder<'a> {
     pub fn new(ui: &'a mut Ui) -> Self {
         let sizing = Sizing::new();
 
-        Self { ui, sizing }
+        Self {
+            ui,
+            sizing,
+            separator: None,
+            separator_frame: false,
+        }
     }
 
     /// Add size hint for column/row
@@ -62,6 +69,23 @@ impl<'a> StripBuilder<'a> {
         self
     }
 
+    /// Paint `stroke` in the inter-cell spacing gaps between this strip's cells.
+    ///
+    /// Each separator line is claimed for the current frame by its exact position, so
+    /// a parent and a nested child strip that share an edge (e.g. a grid built from
+    /// nested strips) paint a single continuous line instead of doubling it.
+    pub fn separators(mut self, stroke: Stroke) -> Self {
+        self.separator = Some(stroke);
+        self
+    }
+
+    /// Whether to also paint a frame around the whole strip when [`Self::separators`]
+    /// is set. Defaults to `false`.
+    pub fn separator_frame(mut self, frame: bool) -> Self {
+        self.separator_frame = frame;
+        self
+    }
+
     /// Build horizontal strip: Cells are positions from left to right.
     /// Takes the available horizontal width, so there can't be anything right of the strip or the container will grow slowly!
     ///
@@ -70,16 +94,30 @@ impl<'a> StripBuilder<'a> {
     where
         F: for<'b> FnOnce(Strip<'a, 'b>),
     {
-        let widths = self.sizing.into_lengths(
-            self.ui.available_rect_before_wrap().width() - self.ui.spacing().item_spacing.x,
-            self.ui.spacing().item_spacing.x,
+        let item_spacing = self.ui.spacing().item_spacing.x;
+        let widths = self.sizing.into_lengths_cached(
+            &*self.ui,
+            self.ui.available_rect_before_wrap().width() - item_spacing,
+            item_spacing,
         );
         let mut layout = Layout::new(self.ui, CellDirection::Horizontal);
         strip(Strip {
             layout: &mut layout,
             direction: CellDirection::Horizontal,
-            sizes: widths,
+            sizes: widths.clone(),
         });
+        if let Some(stroke) = self.separator {
+            let rect = layout.rect();
+            paint_separators(
+                layout.ui(),
+                rect,
+                CellDirection::Horizontal,
+                &widths,
+                item_spacing,
+                stroke,
+                self.separator_frame,
+            );
+        }
         layout.set_rect()
     }
 
@@ -91,18 +129,251 @@ impl<'a> StripBuilder<'a> {
     where
         F: for<'b> FnOnce(Strip<'a, 'b>),
     {
-        let heights = self.sizing.into_lengths(
-            self.ui.available_rect_before_wrap().height() - self.ui.spacing().item_spacing.y,
-            self.ui.spacing().item_spacing.y,
+        let item_spacing = self.ui.spacing().item_spacing.y;
+        let heights = self.sizing.into_lengths_cached(
+            &*self.ui,
+            self.ui.available_rect_before_wrap().height() - item_spacing,
+            item_spacing,
         );
         let mut layout = Layout::new(self.ui, CellDirection::Vertical);
         strip(Strip {
             layout: &mut layout,
             direction: CellDirection::Vertical,
-            sizes: heights,
+            sizes: heights.clone(),
         });
+        if let Some(stroke) = self.separator {
+            let rect = layout.rect();
+            paint_separators(
+                layout.ui(),
+                rect,
+                CellDirection::Vertical,
+                &heights,
+                item_spacing,
+                stroke,
+                self.separator_frame,
+            );
+        }
         layout.set_rect()
     }
+
+    /// Auto-flow `item_widths.len()` cells into as many columns as fit the available
+    /// width, `ls`-style: the largest column count whose packed column widths don't
+    /// overflow wins. `direction` picks the fill order: [`Direction::LeftToRight`] fills
+    /// a row at a time and [`Direction::TopDown`] fills a column at a time, each starting
+    /// from the top-left; [`Direction::RightToLeft`] and [`Direction::BottomUp`] are their
+    /// mirror images, starting from the top-right and bottom-left respectively.
+    ///
+    /// Call this directly on a freshly created [`Self::new`] builder; any sizes added
+    /// with [`Self::size`]/[`Self::sizes`] beforehand are ignored since the column
+    /// widths are derived from `item_widths` instead.
+    ///
+    /// `add_contents` is called once per item with its index into `item_widths`.
+    pub fn grid_auto(
+        self,
+        item_widths: &[f32],
+        row_height: f32,
+        spacing: f32,
+        direction: Direction,
+        mut add_contents: impl FnMut(&mut Ui, usize),
+    ) -> Response {
+        // Each row below is a `horizontal` strip, which reserves one trailing `spacing`
+        // out of the available width; fit against what its cells actually get.
+        let available_width = self.ui.available_rect_before_wrap().width() - spacing;
+        let (columns, col_widths) =
+            fit_auto_grid_columns(item_widths, available_width, spacing, direction);
+        let rows = if columns == 0 {
+            0
+        } else {
+            item_widths.len().div_ceil(columns)
+        };
+        let n = item_widths.len();
+
+        // Column *membership* doesn't depend on which end of the row/column we start
+        // from, only the physical left-to-right order the columns are drawn in does.
+        let mut physical_col_widths = col_widths;
+        if direction == Direction::RightToLeft {
+            physical_col_widths.reverse();
+        }
+
+        // `fit_auto_grid_columns` already sized everything assuming a `spacing` gap; make
+        // that the actual gap the inner strip renders with, instead of whatever
+        // `ui.spacing().item_spacing` happens to be, so the two can't disagree.
+        let ui = self.ui;
+        let previous_spacing = ui.spacing().item_spacing;
+        ui.spacing_mut().item_spacing = vec2(spacing, spacing);
+
+        let response = StripBuilder::new(&mut *ui)
+            .sizes(Size::Absolute(row_height), rows)
+            .vertical(|mut strip| {
+                for row in 0..rows {
+                    strip.strip(|mut builder| {
+                        for &width in &physical_col_widths {
+                            builder = builder.size(Size::Absolute(width));
+                        }
+                        builder.horizontal(|mut row_strip| {
+                            for col in 0..columns {
+                                let index = auto_grid_index(row, col, rows, columns, direction);
+                                if index < n {
+                                    row_strip.cell(|ui| add_contents(ui, index));
+                                } else {
+                                    row_strip.empty();
+                                }
+                            }
+                        });
+                    });
+                }
+            });
+
+        ui.spacing_mut().item_spacing = previous_spacing;
+        response
+    }
+}
+
+/// Map a physical `(row, col)` cell of the rendered grid back to the index into
+/// `item_widths` that belongs there, honoring `direction`'s fill order.
+fn auto_grid_index(row: usize, col: usize, rows: usize, columns: usize, direction: Direction) -> usize {
+    match direction {
+        Direction::TopDown => col * rows + row,
+        Direction::BottomUp => col * rows + (rows - 1 - row),
+        Direction::LeftToRight => row * columns + col,
+        Direction::RightToLeft => row * columns + (columns - 1 - col),
+    }
+}
+
+/// Compute the largest column count (from an `ls`-style upper bound down to `1`) whose
+/// packed column widths fit within `available_width`, along with the width of each
+/// resulting column.
+fn fit_auto_grid_columns(
+    item_widths: &[f32],
+    available_width: f32,
+    spacing: f32,
+    direction: Direction,
+) -> (usize, Vec<f32>) {
+    let n = item_widths.len();
+    if n == 0 {
+        return (0, Vec::new());
+    }
+
+    let min_width = item_widths.iter().copied().fold(f32::INFINITY, f32::min);
+    let max_columns = ((available_width / min_width.max(1.0)).floor() as usize)
+        .clamp(1, n);
+
+    for columns in (1..=max_columns).rev() {
+        let rows = n.div_ceil(columns);
+        let mut col_widths = vec![0.0_f32; columns];
+        for (i, &width) in item_widths.iter().enumerate() {
+            let col = match direction {
+                Direction::TopDown | Direction::BottomUp => i / rows,
+                Direction::LeftToRight | Direction::RightToLeft => i % columns,
+            };
+            col_widths[col] = col_widths[col].max(width);
+        }
+
+        let total_width = col_widths.iter().sum::<f32>() + spacing * (columns - 1) as f32;
+        if total_width <= available_width || columns == 1 {
+            return (columns, col_widths);
+        }
+    }
+
+    unreachable!("the `columns == 1` case above always returns")
+}
+
+/// Paint `stroke` centered in each inter-cell spacing gap between `lengths`, and
+/// optionally a frame around `rect` as a whole. Every line segment is claimed through
+/// [`claim_separator_line`] first, so a line another strip already painted at the exact
+/// same position this frame (e.g. the shared edge of a parent and a nested child strip)
+/// is skipped instead of drawn again.
+fn paint_separators(
+    ui: &Ui,
+    rect: Rect,
+    direction: CellDirection,
+    lengths: &[f32],
+    spacing: f32,
+    stroke: Stroke,
+    frame: bool,
+) {
+    if frame {
+        paint_rect_stroke_deduped(ui, rect, stroke);
+    }
+
+    let ctx = ui.ctx().clone();
+    let frame_nr = ctx.frame_nr();
+    let painter = ui.painter();
+
+    let paint_line_once = |p0: Pos2, p1: Pos2| {
+        if claim_separator_line(&ctx, frame_nr, p0, p1) {
+            painter.line_segment([p0, p1], stroke);
+        }
+    };
+
+    let mut offset = 0.0;
+    for (i, &len) in lengths.iter().enumerate() {
+        offset += len;
+        if i + 1 < lengths.len() {
+            let mid = offset + spacing / 2.0;
+            match direction {
+                CellDirection::Horizontal => {
+                    let x = rect.left() + mid;
+                    paint_line_once(pos2(x, rect.top()), pos2(x, rect.bottom()));
+                }
+                CellDirection::Vertical => {
+                    let y = rect.top() + mid;
+                    paint_line_once(pos2(rect.left(), y), pos2(rect.right(), y));
+                }
+            }
+            offset += spacing;
+        }
+    }
+}
+
+/// Paint `stroke` around `rect`'s four edges, each claimed through
+/// [`claim_separator_line`] first so an edge another bordered cell or separator already
+/// painted this frame at the same position is skipped instead of doubled.
+fn paint_rect_stroke_deduped(ui: &Ui, rect: Rect, stroke: Stroke) {
+    let ctx = ui.ctx().clone();
+    let frame_nr = ctx.frame_nr();
+    let painter = ui.painter();
+
+    let paint_line_once = |p0: Pos2, p1: Pos2| {
+        if claim_separator_line(&ctx, frame_nr, p0, p1) {
+            painter.line_segment([p0, p1], stroke);
+        }
+    };
+
+    paint_line_once(rect.left_top(), rect.right_top());
+    paint_line_once(rect.left_bottom(), rect.right_bottom());
+    paint_line_once(rect.left_top(), rect.left_bottom());
+    paint_line_once(rect.right_top(), rect.right_bottom());
+}
+
+/// Returns `true` the first time this exact line segment (quantized to a quarter point,
+/// endpoint order ignored) is claimed during frame `frame_nr`, and `false` on every
+/// later attempt. Backs [`paint_separators`]'s edge-merging: whichever strip paints a
+/// shared edge first wins, and everyone else sharing that edge skips it.
+fn claim_separator_line(ctx: &Context, frame_nr: u64, p0: Pos2, p1: Pos2) -> bool {
+    fn quantize(p: Pos2) -> (i64, i64) {
+        ((p.x * 4.0).round() as i64, (p.y * 4.0).round() as i64)
+    }
+
+    let (a, b) = (quantize(p0), quantize(p1));
+    let key = if a <= b { (a, b) } else { (b, a) };
+
+    ctx.data_mut(|data| {
+        let painted = data.get_temp_mut_or_insert_with(Id::NULL, PaintedSeparators::default);
+        if painted.frame_nr != frame_nr {
+            painted.frame_nr = frame_nr;
+            painted.lines.clear();
+        }
+        painted.lines.insert(key)
+    })
+}
+
+/// Which separator line segments have already been painted this frame, so
+/// [`claim_separator_line`] can tell a first paint from a later, redundant one.
+#[derive(Clone, Default)]
+struct PaintedSeparators {
+    frame_nr: u64,
+    lines: std::collections::HashSet<((i64, i64), (i64, i64))>,
 }
 
 /// A Strip of cells which go in one direction. Each cell has a fixed size.
@@ -138,14 +409,14 @@ impl<'a, 'b> Strip<'a, 'b> {
         self.layout.empty(width, height);
     }
 
-    fn _cell(&mut self, clip: bool, add_contents: impl FnOnce(&mut Ui)) {
+    fn _cell(&mut self, clip: bool, add_contents: impl FnOnce(&mut Ui)) -> Rect {
         assert!(
             !self.sizes.is_empty(),
             "Tried using more strip cells then available."
         );
 
         let (width, height) = self.next_cell_size();
-        self.layout.add(width, height, clip, add_contents);
+        self.layout.add(width, height, clip, add_contents)
     }
 
     /// Add cell, content is wrapped
@@ -158,6 +429,15 @@ impl<'a, 'b> Strip<'a, 'b> {
         self._cell(true, add_contents);
     }
 
+    /// Add a cell and paint `stroke` around it, independent of whether the strip as a
+    /// whole has [`StripBuilder::separators`] set. Shares [`claim_separator_line`] with
+    /// [`StripBuilder::separators`], so a bordered cell sitting flush against another
+    /// bordered cell or a separator doesn't double-paint their shared edge.
+    pub fn cell_bordered(&mut self, stroke: Stroke, add_contents: impl FnOnce(&mut Ui)) {
+        let rect = self._cell(false, add_contents);
+        paint_rect_stroke_deduped(self.layout.ui(), rect, stroke);
+    }
+
     fn _strip(&mut self, clip: bool, strip_builder: impl FnOnce(StripBuilder<'_>)) {
         self._cell(clip, |ui| {
             strip_builder(StripBuilder::new(ui));
@@ -180,4 +460,98 @@ impl<'a, 'b> Drop for Strip<'a, 'b> {
             self.empty();
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{auto_grid_index, claim_separator_line, fit_auto_grid_columns};
+    use egui::{pos2, Direction};
+
+    #[test]
+    fn claim_separator_line_only_the_first_caller_wins() {
+        egui::__run_test_ui(|ui| {
+            let ctx = ui.ctx().clone();
+            let frame_nr = ctx.frame_nr();
+            let (p0, p1) = (pos2(10.0, 0.0), pos2(10.0, 50.0));
+
+            assert!(
+                claim_separator_line(&ctx, frame_nr, p0, p1),
+                "the first strip to paint this edge should claim it"
+            );
+            assert!(
+                !claim_separator_line(&ctx, frame_nr, p0, p1),
+                "a second strip sharing the exact same edge must not repaint it"
+            );
+            // Drawn from the opposite end, it's still the same edge.
+            assert!(!claim_separator_line(&ctx, frame_nr, p1, p0));
+        });
+    }
+
+    #[test]
+    fn claim_separator_line_resets_on_a_new_frame() {
+        egui::__run_test_ui(|ui| {
+            let ctx = ui.ctx().clone();
+            let (p0, p1) = (pos2(0.0, 10.0), pos2(50.0, 10.0));
+
+            assert!(claim_separator_line(&ctx, 0, p0, p1));
+            assert!(!claim_separator_line(&ctx, 0, p0, p1));
+            assert!(
+                claim_separator_line(&ctx, 1, p0, p1),
+                "a new frame number should clear last frame's claims"
+            );
+        });
+    }
+
+    #[test]
+    fn auto_grid_index_mirrors_top_down_and_left_to_right() {
+        // A 2-column, 3-row grid: index 0..6, column-major.
+        assert_eq!(auto_grid_index(0, 0, 3, 2, Direction::TopDown), 0);
+        assert_eq!(auto_grid_index(2, 0, 3, 2, Direction::TopDown), 2);
+        assert_eq!(auto_grid_index(0, 1, 3, 2, Direction::TopDown), 3);
+        // BottomUp fills the same columns but starting from the bottom row.
+        assert_eq!(auto_grid_index(2, 0, 3, 2, Direction::BottomUp), 0);
+        assert_eq!(auto_grid_index(0, 0, 3, 2, Direction::BottomUp), 2);
+
+        // A 3-column, 2-row grid: index 0..6, row-major.
+        assert_eq!(auto_grid_index(0, 0, 2, 3, Direction::LeftToRight), 0);
+        assert_eq!(auto_grid_index(0, 2, 2, 3, Direction::LeftToRight), 2);
+        // RightToLeft fills the same rows but starting from the rightmost column.
+        assert_eq!(auto_grid_index(0, 2, 2, 3, Direction::RightToLeft), 0);
+        assert_eq!(auto_grid_index(0, 0, 2, 3, Direction::RightToLeft), 2);
+    }
+
+    #[test]
+    fn fit_auto_grid_columns_packs_as_many_as_fit() {
+        let widths = [40.0, 40.0, 40.0, 40.0, 40.0];
+        let (columns, col_widths) =
+            fit_auto_grid_columns(&widths, 130.0, 10.0, Direction::LeftToRight);
+        // 3 columns of 40 + 2 gaps of 10 = 140 > 130, so only 2 columns (40+10+40=90) fit.
+        assert_eq!(columns, 2);
+        assert_eq!(col_widths, vec![40.0, 40.0]);
+    }
+
+    #[test]
+    fn fit_auto_grid_columns_falls_back_to_one_column_when_nothing_else_fits() {
+        let widths = [500.0, 500.0];
+        let (columns, col_widths) =
+            fit_auto_grid_columns(&widths, 100.0, 10.0, Direction::LeftToRight);
+        assert_eq!(columns, 1);
+        assert_eq!(col_widths, vec![500.0]);
+    }
+
+    #[test]
+    fn fit_auto_grid_columns_uses_widest_item_per_column() {
+        let widths = [10.0, 90.0, 10.0, 90.0];
+        let (columns, col_widths) =
+            fit_auto_grid_columns(&widths, 110.0, 10.0, Direction::LeftToRight);
+        assert_eq!(columns, 2);
+        assert_eq!(col_widths, vec![10.0, 90.0]);
+    }
+
+    #[test]
+    fn fit_auto_grid_columns_empty_input() {
+        let (columns, col_widths) = fit_auto_grid_columns(&[], 100.0, 10.0, Direction::LeftToRight);
+        assert_eq!(columns, 0);
+        assert!(col_widths.is_empty());
+    }
 }
\ No newline at end of file